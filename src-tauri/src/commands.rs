@@ -1,25 +1,80 @@
+mod backend;
+mod history;
+mod request_opts;
+
+pub use backend::{Backend, GenParams};
+pub use history::{get_recording, list_recordings, search_transcripts};
+pub use request_opts::RequestOpts;
+
+use std::time::Duration;
+
+use futures_util::StreamExt;
 use serde_json::Value;
 use tauri::command;
+use tauri::ipc::Channel;
 use tauri::{AppHandle, Runtime};
+use tauri_plugin_shell::process::CommandEvent;
 use tauri_plugin_shell::ShellExt;
 
-/// Transcribes an audio file using the Python Sidecar.
+/// Loads an API token, checking the process environment first and falling
+/// back to a `<key>=<value>` line in `../.credentials` or `.credentials`
+/// (useful for dev/portable setups that shouldn't pollute global env vars).
+pub(crate) fn load_api_token(key: &str) -> Option<String> {
+    if let Ok(token) = std::env::var(key) {
+        if !token.is_empty() {
+            return Some(token);
+        }
+    }
+
+    let prefix = format!("{}=", key);
+    for path in ["../.credentials", ".credentials"] {
+        if let Ok(content) = std::fs::read_to_string(path) {
+            for line in content.lines() {
+                if let Some(value) = line.strip_prefix(&prefix) {
+                    let token = value.trim_matches('"');
+                    if !token.is_empty() {
+                        println!("Loaded {} from {}", key, path);
+                        return Some(token.to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Transcribes an audio file using the Python Sidecar, streaming progress
+/// as it goes instead of blocking until the whole file is done.
 ///
-/// Spawns the `api-sidecar` binary as a child process.
-/// It automatically injects the `HF_TOKEN` environment variable if found
-/// in the system environment or a local `.credentials` file.
+/// Spawns the `api-sidecar` binary as a child process. It automatically
+/// injects the `HF_TOKEN` environment variable if found in the system
+/// environment or a local `.credentials` file.
+///
+/// The sidecar's stdout contract is newline-delimited JSON: one segment
+/// object per line, shaped `{"start": <ms>, "end": <ms>, "text": <str>}`.
+/// Each decoded segment is pushed to `on_event` as soon as its line is
+/// complete (a line split across two stdout chunks is buffered and
+/// completed on the next chunk); stderr lines are pushed too, wrapped as
+/// `{"stderr": "<line>"}`, as diagnostic/progress output.
 ///
 /// # Arguments
 /// * `app` - The Tauri App Handle (used to spawn sidecar).
 /// * `file_path` - Absolute path to the .webm audio file.
+/// * `request_opts` - Timeout/retry tunables; `timeout_secs` bounds how long
+///   the sidecar is allowed to run before the call fails instead of hanging.
+/// * `on_event` - Channel segments and stderr diagnostics are pushed to as
+///   soon as the sidecar prints them.
 ///
 /// # Returns
-/// * `Ok(Value)` - JSON object containing transcribed segments.
-/// * `Err(String)` - Error message if sidecar fails or file is missing.
+/// * `Ok(Value)` - `{"segments": [...]}`, the full aggregated transcript.
+/// * `Err(String)` - Error message if sidecar fails, times out, or the file is missing.
 #[command]
 pub async fn transcribe_audio<R: Runtime>(
     app: AppHandle<R>,
     file_path: String,
+    request_opts: RequestOpts,
+    on_event: Channel<Value>,
 ) -> Result<Value, String> {
     println!("Invoking transcription for: {}", file_path);
 
@@ -28,71 +83,184 @@ pub async fn transcribe_audio<R: Runtime>(
         .sidecar("api-sidecar")
         .map_err(|e| e.to_string())?;
 
-    if let Ok(token) = std::env::var("HF_TOKEN") {
+    if let Some(token) = load_api_token("HF_TOKEN") {
         sidecar_command = sidecar_command.env("HF_TOKEN", token);
-    } else {
-        // Fallback: Check for a local `.credentials` file (useful for dev/portable setups).
-        // This allows users to provide tokens without polluting global env vars.
-        let paths = vec!["../.credentials", ".credentials"];
-        for path in paths {
-            if let Ok(content) = std::fs::read_to_string(path) {
-                for line in content.lines() {
-                    if line.starts_with("HF_TOKEN=") {
-                        let token = line.trim_start_matches("HF_TOKEN=").trim_matches('"');
-                        if !token.is_empty() {
-                            println!("Loaded HF_TOKEN from {}", path);
-                            sidecar_command = sidecar_command.env("HF_TOKEN", token);
-                            break;
+    }
+
+    let (mut rx, child) = sidecar_command
+        .args(&[&file_path])
+        .spawn()
+        .map_err(|e| e.to_string())?;
+
+    let mut segments: Vec<Value> = Vec::new();
+    let mut stdout_buffer: Vec<u8> = Vec::new();
+    let mut stderr_text = String::new();
+    let mut exit_success = false;
+
+    let drain_events = async {
+        while let Some(event) = rx.recv().await {
+            match event {
+                CommandEvent::Stdout(bytes) => {
+                    stdout_buffer.extend_from_slice(&bytes);
+                    while let Some(newline_pos) = stdout_buffer.iter().position(|&b| b == b'\n') {
+                        let line: Vec<u8> = stdout_buffer.drain(..=newline_pos).collect();
+                        let line = String::from_utf8_lossy(&line);
+                        let line = line.trim();
+                        if line.is_empty() {
+                            continue;
+                        }
+
+                        match serde_json::from_str::<Value>(line) {
+                            Ok(segment) => {
+                                let _ = on_event.send(segment.clone());
+                                segments.push(segment);
+                            }
+                            Err(e) => {
+                                println!("Failed to parse sidecar segment: {}. Line was: {}", e, line);
+                            }
                         }
                     }
                 }
+                CommandEvent::Stderr(bytes) => {
+                    let line = String::from_utf8_lossy(&bytes).trim().to_string();
+                    if !line.is_empty() {
+                        stderr_text.push_str(&line);
+                        stderr_text.push('\n');
+                        let _ = on_event.send(serde_json::json!({ "stderr": line }));
+                    }
+                }
+                CommandEvent::Terminated(payload) => {
+                    exit_success = payload.code == Some(0);
+                }
+                _ => {}
             }
         }
-    }
+    };
 
-    let output = sidecar_command
-        .args(&[&file_path])
-        .output()
+    if tokio::time::timeout(Duration::from_secs(request_opts.timeout_secs), drain_events)
         .await
-        .map_err(|e| e.to_string())?;
+        .is_err()
+    {
+        let _ = child.kill();
+        return Err(format!("Sidecar timed out after {}s", request_opts.timeout_secs));
+    }
 
-    if output.status.success() {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        // Parse the JSON output from the sidecar
-        let result: Value = serde_json::from_str(&stdout).map_err(|e| {
-            format!(
-                "Failed to parse sidecar output: {}. Output was: {}",
-                e, stdout
-            )
+    if !exit_success {
+        return Err(format!("Sidecar failed: {}", stderr_text));
+    }
+
+    let result = serde_json::json!({ "segments": segments });
+
+    if let Some((pool, recording_id)) = history::recording_for(&app, &file_path).await {
+        if let Err(e) = history::insert_segments(&pool, recording_id, &segments).await {
+            println!("Failed to record transcript segments for {}: {}", file_path, e);
+        }
+    }
+
+    Ok(result)
+}
+
+/// Generates a concise summary of the provided text using a configurable
+/// inference backend.
+///
+/// `base_url` is combined with the backend's own endpoint path (e.g.
+/// `/api/generate` for Ollama, `/v1/chat/completions` for an
+/// OpenAI-compatible server) to build the request URL, so this works
+/// equally well against a local Ollama instance or a remote model host.
+///
+/// # Arguments
+/// * `app` - The Tauri App Handle (used to persist the summary).
+/// * `text` - The full transcript text to summarize.
+/// * `recording_file_path` - Path of the recording this transcript belongs
+///   to, used to link the stored summary back to its `recordings` row.
+/// * `backend` - Which inference backend to talk to.
+/// * `base_url` - Base URL of the backend, e.g. `http://localhost:11434`.
+/// * `params` - Generation parameters (max tokens, temperature, etc).
+/// * `request_opts` - Connect/overall timeouts, redirect limit, and retry policy.
+///
+/// # Returns
+/// * `Ok(String)` - The generated summary text.
+/// * `Err(String)` - Network error, timeout, or backend API failure message.
+#[command]
+pub async fn summarize_text<R: Runtime>(
+    app: AppHandle<R>,
+    text: String,
+    recording_file_path: String,
+    backend: Backend,
+    base_url: String,
+    params: GenParams,
+    request_opts: RequestOpts,
+) -> Result<String, String> {
+    println!("Summarizing text (length: {}, backend: {:?})", text.len(), backend);
+
+    let url = format!(
+        "{}{}",
+        base_url.trim_end_matches('/'),
+        backend.endpoint_path()
+    );
+    let prompt = format!("Summarize the following text concisely:\n\n{}", text);
+
+    let client = request_opts.build_client()?;
+    let res = client
+        .post(url)
+        .headers(backend.build_headers())
+        .json(&backend.build_body(&prompt, &params))
+        .send()
+        .await
+        .map_err(|e| {
+            println!("Backend request failed: {}", e);
+            e.to_string()
         })?;
-        Ok(result)
+
+    let status = res.status();
+    if status.is_success() {
+        let body: Value = res.json().await.map_err(|e| e.to_string())?;
+        let summary = backend.parse_response(body)?;
+        println!("Summarization successful");
+
+        if let Some((pool, recording_id)) = history::recording_for(&app, &recording_file_path).await {
+            if let Err(e) = history::insert_summary(&pool, recording_id, &summary).await {
+                println!("Failed to record summary history for {}: {}", recording_file_path, e);
+            }
+        }
+
+        Ok(summary)
     } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(format!("Sidecar failed: {}", stderr))
+        println!("Backend API returned error: {}", status);
+        Err(format!("Backend API error: {}", status))
     }
 }
 
-/// Generates a concise summary of the provided text using a local Ollama instance.
+/// Streams a summary of the provided text from Ollama, pushing each
+/// generated token delta to the frontend as soon as it arrives.
 ///
-/// Connects to `http://localhost:11434/api/generate` and uses the
-/// `qwen2.5-coder:7b` model to process the transcript.
+/// Connects to `http://localhost:11434/api/generate` with `"stream": true`
+/// and reads the response body as newline-delimited JSON objects, each
+/// shaped `{"response": "<delta>", "done": false}` with a final
+/// `{"done": true}` marking the end of the stream.
 ///
 /// # Arguments
 /// * `text` - The full transcript text to summarize.
+/// * `on_token` - Channel the decoded `response` deltas are pushed to.
+/// * `request_opts` - Connect/overall timeouts, redirect limit, and retry policy.
 ///
 /// # Returns
-/// * `Ok(String)` - The generated summary text.
-/// * `Err(String)` - Network error or Ollama API failure message.
+/// * `Ok(())` - Once the stream has been fully consumed.
+/// * `Err(String)` - Network error, timeout, or Ollama API failure message.
 #[command]
-pub async fn summarize_text(text: String) -> Result<String, String> {
-    println!("Summarizing text (length: {})", text.len());
-    let client = reqwest::Client::new();
+pub async fn summarize_text_streaming(
+    text: String,
+    on_token: Channel<String>,
+    request_opts: RequestOpts,
+) -> Result<(), String> {
+    println!("Summarizing text (streaming, length: {})", text.len());
+    let client = request_opts.build_client()?;
     let res = client
         .post("http://localhost:11434/api/generate")
         .json(&serde_json::json!({
             "model": "qwen2.5-coder:7b",
             "prompt": format!("Summarize the following text concisely:\n\n{}", text),
-            "stream": false
+            "stream": true
         }))
         .send()
         .await
@@ -102,22 +270,47 @@ pub async fn summarize_text(text: String) -> Result<String, String> {
         })?;
 
     let status = res.status();
-    if status.is_success() {
-        let body: Value = res.json().await.map_err(|e| e.to_string())?;
-        match body["response"].as_str() {
-            Some(response) => {
-                println!("Summarization successful");
-                Ok(response.to_string())
+    if !status.is_success() {
+        println!("Ollama API returned error: {}", status);
+        return Err(format!("Ollama API error: {}", status));
+    }
+
+    let mut stream = res.bytes_stream();
+    let mut buffer = String::new();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| e.to_string())?;
+        buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+        while let Some(newline_pos) = buffer.find('\n') {
+            let line = buffer[..newline_pos].trim().to_string();
+            buffer.drain(..=newline_pos);
+
+            if line.is_empty() {
+                continue;
             }
-            None => {
-                println!("Ollama response missing 'response' field");
-                Err("Ollama response missing 'response' field".to_string())
+
+            let parsed: Value = match serde_json::from_str(&line) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    println!("Failed to parse Ollama chunk: {}. Line was: {}", e, line);
+                    continue;
+                }
+            };
+
+            if let Some(delta) = parsed["response"].as_str() {
+                on_token.send(delta.to_string()).map_err(|e| e.to_string())?;
+            }
+
+            if parsed["done"].as_bool().unwrap_or(false) {
+                println!("Summarization stream finished");
+                return Ok(());
             }
         }
-    } else {
-        println!("Ollama API returned error: {}", status);
-        Err(format!("Ollama API error: {}", status))
     }
+
+    println!("Summarization stream ended without an explicit done marker");
+    Ok(())
 }
 
 /// Saves the audio payload to the AppData/recordings directory.
@@ -171,5 +364,8 @@ pub async fn save_audio<R: Runtime>(
 
     let absolute_path = file_path.to_string_lossy().to_string();
     println!("File saved successfully to: {}", absolute_path);
+
+    history::recording_for(&app, &absolute_path).await;
+
     Ok(absolute_path)
 }
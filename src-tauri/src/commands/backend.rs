@@ -0,0 +1,138 @@
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, USER_AGENT};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::load_api_token;
+
+/// Generation parameters shared across inference backends.
+///
+/// Not every backend honors every field (Ollama has no `do_sample` knob,
+/// for instance), but keeping one shape lets `summarize_text` stay
+/// backend-agnostic.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GenParams {
+    pub model: String,
+    pub max_new_tokens: u32,
+    pub temperature: f32,
+    pub top_p: f32,
+    pub do_sample: bool,
+    pub stop_tokens: Vec<String>,
+}
+
+impl Default for GenParams {
+    fn default() -> Self {
+        Self {
+            model: "qwen2.5-coder:7b".to_string(),
+            max_new_tokens: 512,
+            temperature: 0.7,
+            top_p: 0.9,
+            do_sample: true,
+            stop_tokens: Vec::new(),
+        }
+    }
+}
+
+/// The inference backend a summarization request is sent to.
+///
+/// Each variant knows how to shape its own request body and headers, and
+/// how to pull the generated text back out of the response, so
+/// `summarize_text` can stay oblivious to the differences between a local
+/// Ollama instance and a remote OpenAI-compatible or TGI endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Backend {
+    Ollama,
+    OpenAiCompatible,
+    HuggingFaceTgi,
+}
+
+impl Backend {
+    /// Path appended to the backend's base URL to reach its generate endpoint.
+    pub fn endpoint_path(&self) -> &'static str {
+        match self {
+            Backend::Ollama => "/api/generate",
+            Backend::OpenAiCompatible => "/v1/chat/completions",
+            Backend::HuggingFaceTgi => "/generate",
+        }
+    }
+
+    /// Builds the JSON request body for this backend's generate endpoint.
+    pub fn build_body(&self, prompt: &str, params: &GenParams) -> Value {
+        match self {
+            Backend::Ollama => serde_json::json!({
+                "model": params.model,
+                "prompt": prompt,
+                "stream": false,
+                "options": {
+                    "temperature": params.temperature,
+                    "top_p": params.top_p,
+                    "num_predict": params.max_new_tokens,
+                    "stop": params.stop_tokens,
+                }
+            }),
+            Backend::OpenAiCompatible => serde_json::json!({
+                "model": params.model,
+                "messages": [
+                    { "role": "user", "content": prompt }
+                ],
+                "max_tokens": params.max_new_tokens,
+                "temperature": params.temperature,
+                "top_p": params.top_p,
+                "stop": params.stop_tokens,
+            }),
+            Backend::HuggingFaceTgi => serde_json::json!({
+                "inputs": prompt,
+                "parameters": {
+                    "max_new_tokens": params.max_new_tokens,
+                    "temperature": params.temperature,
+                    "do_sample": params.do_sample,
+                    "top_p": params.top_p,
+                    "stop": params.stop_tokens,
+                }
+            }),
+        }
+    }
+
+    /// Builds the request headers for this backend, sourcing an API token
+    /// from the environment or `.credentials` file when the backend needs one.
+    pub fn build_headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(USER_AGENT, HeaderValue::from_static("LocalScribe"));
+
+        let token = match self {
+            Backend::Ollama => None,
+            Backend::OpenAiCompatible => load_api_token("OPENAI_API_KEY"),
+            Backend::HuggingFaceTgi => load_api_token("HF_TOKEN"),
+        };
+
+        if let Some(token) = token {
+            if let Ok(value) = HeaderValue::from_str(&format!("Bearer {}", token)) {
+                headers.insert(AUTHORIZATION, value);
+            }
+        }
+
+        headers
+    }
+
+    /// Extracts the generated text from this backend's response body.
+    pub fn parse_response(&self, body: Value) -> Result<String, String> {
+        match self {
+            Backend::Ollama => body["response"]
+                .as_str()
+                .map(|s| s.to_string())
+                .ok_or_else(|| "Ollama response missing 'response' field".to_string()),
+            Backend::OpenAiCompatible => body["choices"][0]["message"]["content"]
+                .as_str()
+                .map(|s| s.to_string())
+                .ok_or_else(|| {
+                    "OpenAI-compatible response missing choices[0].message.content".to_string()
+                }),
+            Backend::HuggingFaceTgi => body
+                .get(0)
+                .and_then(|first| first["generated_text"].as_str())
+                .or_else(|| body["generated_text"].as_str())
+                .map(|s| s.to_string())
+                .ok_or_else(|| "TGI response missing 'generated_text' field".to_string()),
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::SqlitePool;
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_sql::{DbInstances, DbPool};
+
+use crate::db::DB_URL;
+
+/// A recording's metadata, as surfaced to the frontend's history view.
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct RecordingSummary {
+    pub id: i64,
+    pub file_path: String,
+    pub duration_secs: Option<f64>,
+    pub created_at: String,
+    pub has_summary: bool,
+}
+
+/// A single matched transcript segment, with enough context to jump back
+/// to the recording it came from.
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct TranscriptMatch {
+    pub recording_id: i64,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub text: String,
+}
+
+/// Borrows the SQLite pool `tauri_plugin_sql` already opened (and ran
+/// migrations against) for `DB_URL`, instead of opening a second, separate
+/// connection — this is the same pool the plugin's own `sql:execute`/
+/// `sql:select` commands use, so there's no risk of the two resolving to
+/// different files, and no per-call connection churn.
+pub(crate) async fn pool<R: Runtime>(app: &AppHandle<R>) -> Result<SqlitePool, String> {
+    let instances = app.state::<DbInstances>();
+    let instances = instances.0.lock().await;
+
+    match instances.get(DB_URL) {
+        Some(DbPool::Sqlite(pool)) => Ok(pool.clone()),
+        Some(_) => Err(format!("'{}' is not a SQLite database", DB_URL)),
+        None => Err(format!("database '{}' has not been connected yet", DB_URL)),
+    }
+}
+
+/// Inserts a `recordings` row for `file_path` if one doesn't already exist,
+/// and returns its id either way.
+pub(crate) async fn upsert_recording(pool: &SqlitePool, file_path: &str) -> Result<i64, String> {
+    sqlx::query("INSERT OR IGNORE INTO recordings (file_path) VALUES (?)")
+        .bind(file_path)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    sqlx::query_scalar("SELECT id FROM recordings WHERE file_path = ?")
+        .bind(file_path)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Opens the managed pool and upserts a recording row for `file_path`,
+/// logging and returning `None` on any failure instead of propagating it —
+/// history is best-effort and must never turn an already-successful
+/// recording/transcription/summary into a reported failure.
+pub(crate) async fn recording_for<R: Runtime>(
+    app: &AppHandle<R>,
+    file_path: &str,
+) -> Option<(SqlitePool, i64)> {
+    let pool = match pool(app).await {
+        Ok(pool) => pool,
+        Err(e) => {
+            println!("Failed to open history database: {}", e);
+            return None;
+        }
+    };
+
+    match upsert_recording(&pool, file_path).await {
+        Ok(recording_id) => Some((pool, recording_id)),
+        Err(e) => {
+            println!("Failed to record recording history for {}: {}", file_path, e);
+            None
+        }
+    }
+}
+
+/// Inserts one `segments` row per entry in the sidecar's `segments` array
+/// (each shaped `{"start": <ms>, "end": <ms>, "text": <str>}`), then derives
+/// the recording's duration from the furthest `end` timestamp seen.
+pub(crate) async fn insert_segments(
+    pool: &SqlitePool,
+    recording_id: i64,
+    segments: &[Value],
+) -> Result<(), String> {
+    let mut duration_ms: i64 = 0;
+
+    for segment in segments {
+        let start_ms = segment["start"].as_i64().unwrap_or(0);
+        let end_ms = segment["end"].as_i64().unwrap_or(0);
+        let text = segment["text"].as_str().unwrap_or_default();
+        duration_ms = duration_ms.max(end_ms);
+
+        sqlx::query(
+            "INSERT INTO segments (recording_id, start_ms, end_ms, text) VALUES (?, ?, ?, ?)",
+        )
+        .bind(recording_id)
+        .bind(start_ms)
+        .bind(end_ms)
+        .bind(text)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+
+    if duration_ms > 0 {
+        sqlx::query("UPDATE recordings SET duration_secs = ? WHERE id = ?")
+            .bind(duration_ms as f64 / 1000.0)
+            .bind(recording_id)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Inserts a `summaries` row for `recording_id` and flips `has_summary` on
+/// its `recordings` row.
+pub(crate) async fn insert_summary(
+    pool: &SqlitePool,
+    recording_id: i64,
+    text: &str,
+) -> Result<(), String> {
+    sqlx::query("INSERT INTO summaries (recording_id, text) VALUES (?, ?)")
+        .bind(recording_id)
+        .bind(text)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    sqlx::query("UPDATE recordings SET has_summary = 1 WHERE id = ?")
+        .bind(recording_id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Lists recordings, most recent first.
+#[tauri::command]
+pub async fn list_recordings<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<Vec<RecordingSummary>, String> {
+    let pool = pool(&app).await?;
+    sqlx::query_as::<_, RecordingSummary>(
+        "SELECT id, file_path, duration_secs, created_at, has_summary \
+         FROM recordings ORDER BY created_at DESC",
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Fetches a single recording's metadata by id.
+#[tauri::command]
+pub async fn get_recording<R: Runtime>(
+    app: AppHandle<R>,
+    id: i64,
+) -> Result<RecordingSummary, String> {
+    let pool = pool(&app).await?;
+    sqlx::query_as::<_, RecordingSummary>(
+        "SELECT id, file_path, duration_secs, created_at, has_summary \
+         FROM recordings WHERE id = ?",
+    )
+    .bind(id)
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Full-text searches past transcripts for `query`, returning matching
+/// segments ordered by their recording's recency.
+#[tauri::command]
+pub async fn search_transcripts<R: Runtime>(
+    app: AppHandle<R>,
+    query: String,
+) -> Result<Vec<TranscriptMatch>, String> {
+    let pool = pool(&app).await?;
+    // Quote the query as a single FTS5 phrase so user input containing
+    // FTS5 syntax (column filters, `NOT`/`AND`/`OR`, a leading `-`, stray
+    // quotes) is matched literally instead of raising a MATCH syntax error.
+    let phrase = format!("\"{}\"", query.replace('"', "\"\""));
+    sqlx::query_as::<_, TranscriptMatch>(
+        "SELECT segments.recording_id, segments.start_ms, segments.end_ms, segments.text \
+         FROM segments_fts \
+         JOIN segments ON segments.id = segments_fts.rowid \
+         WHERE segments_fts MATCH ? \
+         ORDER BY segments.recording_id DESC",
+    )
+    .bind(phrase)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| e.to_string())
+}
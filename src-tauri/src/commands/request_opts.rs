@@ -0,0 +1,49 @@
+use std::time::Duration;
+
+use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
+use reqwest_retry::policies::ExponentialBackoff;
+use reqwest_retry::RetryTransientMiddleware;
+use serde::Deserialize;
+
+/// Tunables for outbound HTTP requests to inference backends, so a
+/// cold-starting Ollama server or a slow remote backend produces a clear
+/// timeout error with bounded retries instead of hanging the UI.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RequestOpts {
+    pub connect_timeout_secs: u64,
+    pub timeout_secs: u64,
+    pub max_redirections: usize,
+    pub max_retries: u32,
+}
+
+impl Default for RequestOpts {
+    fn default() -> Self {
+        Self {
+            connect_timeout_secs: 5,
+            timeout_secs: 30,
+            max_redirections: 5,
+            max_retries: 3,
+        }
+    }
+}
+
+impl RequestOpts {
+    /// Builds a `reqwest` client wrapped with retry middleware configured
+    /// from these options: connect/overall timeouts, a bounded redirect
+    /// policy, and exponential-backoff retries for transient failures
+    /// (connection refused, request timeouts, 5xx responses).
+    pub fn build_client(&self) -> Result<ClientWithMiddleware, String> {
+        let inner = reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(self.connect_timeout_secs))
+            .timeout(Duration::from_secs(self.timeout_secs))
+            .redirect(reqwest::redirect::Policy::limited(self.max_redirections))
+            .build()
+            .map_err(|e| e.to_string())?;
+
+        let retry_policy = ExponentialBackoff::builder().build_with_max_retries(self.max_retries);
+
+        Ok(ClientBuilder::new(inner)
+            .with(RetryTransientMiddleware::new_with_policy(retry_policy))
+            .build())
+    }
+}
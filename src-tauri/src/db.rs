@@ -0,0 +1,54 @@
+use tauri_plugin_sql::{Migration, MigrationKind};
+
+/// The `sqlite:` URL this database is registered under, via
+/// `tauri_plugin_sql::Builder::add_migrations` in `lib.rs`. `commands::history`
+/// looks the resulting pool up from the plugin's managed state under this
+/// exact same string, so the two can never resolve to different files.
+pub const DB_URL: &str = "sqlite:localscribe.db";
+
+/// Migrations registered with `tauri_plugin_sql::Builder::add_migrations`,
+/// run automatically at startup.
+///
+/// `recordings` holds one row per saved audio file, `segments` holds the
+/// per-utterance transcript pieces produced by the sidecar, and `summaries`
+/// holds the backend-generated summary text, all linked by `recording_id`.
+/// A `segments_fts` FTS5 shadow table backs `search_transcripts`.
+pub fn migrations() -> Vec<Migration> {
+    vec![Migration {
+        version: 1,
+        description: "create recordings, segments, and summaries tables",
+        sql: r#"
+            CREATE TABLE recordings (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                file_path TEXT NOT NULL UNIQUE,
+                duration_secs REAL,
+                created_at TEXT NOT NULL DEFAULT (datetime('now')),
+                has_summary INTEGER NOT NULL DEFAULT 0
+            );
+
+            CREATE TABLE segments (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                recording_id INTEGER NOT NULL REFERENCES recordings(id) ON DELETE CASCADE,
+                start_ms INTEGER NOT NULL,
+                end_ms INTEGER NOT NULL,
+                text TEXT NOT NULL
+            );
+
+            CREATE TABLE summaries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                recording_id INTEGER NOT NULL REFERENCES recordings(id) ON DELETE CASCADE,
+                text TEXT NOT NULL,
+                created_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );
+
+            CREATE VIRTUAL TABLE segments_fts USING fts5(
+                text, content='segments', content_rowid='id'
+            );
+
+            CREATE TRIGGER segments_ai AFTER INSERT ON segments BEGIN
+                INSERT INTO segments_fts(rowid, text) VALUES (new.id, new.text);
+            END;
+        "#,
+        kind: MigrationKind::Up,
+    }]
+}
@@ -1,6 +1,8 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 
 mod commands;
+mod db;
+mod protocol;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -8,10 +10,26 @@ pub fn run() {
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_http::init())
-        .plugin(tauri_plugin_sql::Builder::default().build())
+        .plugin(
+            tauri_plugin_sql::Builder::default()
+                .add_migrations(db::DB_URL, db::migrations())
+                .build(),
+        )
+        .register_asynchronous_uri_scheme_protocol("recording", |ctx, request, responder| {
+            let app = ctx.app_handle().clone();
+            // File I/O must not block the thread the protocol handler runs
+            // on; do the synchronous read on the blocking pool instead.
+            tauri::async_runtime::spawn_blocking(move || {
+                responder.respond(protocol::handle_recording_request(&app, &request));
+            });
+        })
         .invoke_handler(tauri::generate_handler![
             commands::transcribe_audio,
-            commands::summarize_text
+            commands::summarize_text,
+            commands::summarize_text_streaming,
+            commands::list_recordings,
+            commands::get_recording,
+            commands::search_transcripts
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
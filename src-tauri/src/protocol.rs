@@ -0,0 +1,137 @@
+use std::borrow::Cow;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::PathBuf;
+
+use tauri::http::{Request, Response, StatusCode};
+use tauri::{AppHandle, Manager, Runtime};
+
+/// Builds the HTTP response for a `recording://localhost/<filename>` request
+/// (the filename is read from the URI path), serving files out of the
+/// AppData/recordings directory that `save_audio` writes to. Honors byte-range requests so an `<audio>` element can seek a long
+/// recording without the webview loading the whole file into memory.
+pub fn handle_recording_request<R: Runtime>(
+    app: &AppHandle<R>,
+    request: &Request<Vec<u8>>,
+) -> Response<Cow<'static, [u8]>> {
+    match resolve_recording_path(app, request.uri().path()) {
+        Ok(path) => serve_file(&path, request),
+        Err(status) => empty_response(status),
+    }
+}
+
+/// Resolves the requested filename against the recordings directory,
+/// rejecting path traversal attempts (`..` or nested path separators).
+fn resolve_recording_path<R: Runtime>(
+    app: &AppHandle<R>,
+    request_path: &str,
+) -> Result<PathBuf, StatusCode> {
+    let filename = request_path.trim_start_matches('/');
+
+    if filename.is_empty() || filename.contains("..") || filename.contains('/') || filename.contains('\\') {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let path = app_data_dir.join("recordings").join(filename);
+
+    if !path.is_file() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    Ok(path)
+}
+
+/// Reads `path` and builds either a full `200` response or a `206 Partial
+/// Content` response honoring the request's `Range: bytes=start-end` header.
+fn serve_file(path: &PathBuf, request: &Request<Vec<u8>>) -> Response<Cow<'static, [u8]>> {
+    let mut file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(_) => return empty_response(StatusCode::NOT_FOUND),
+    };
+
+    let total_len = match file.metadata() {
+        Ok(metadata) => metadata.len(),
+        Err(_) => return empty_response(StatusCode::INTERNAL_SERVER_ERROR),
+    };
+
+    let range = request
+        .headers()
+        .get("range")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| parse_range(value, total_len));
+
+    match range {
+        Some((start, end)) => {
+            let len = (end - start + 1) as usize;
+            let mut buffer = vec![0u8; len];
+            if file.seek(SeekFrom::Start(start)).is_err() || file.read_exact(&mut buffer).is_err() {
+                return empty_response(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+
+            Response::builder()
+                .status(StatusCode::PARTIAL_CONTENT)
+                .header("Content-Type", "audio/webm")
+                .header("Accept-Ranges", "bytes")
+                .header("Content-Range", format!("bytes {}-{}/{}", start, end, total_len))
+                .header("Content-Length", len.to_string())
+                .body(Cow::Owned(buffer))
+                .unwrap()
+        }
+        None => {
+            let mut buffer = Vec::with_capacity(total_len as usize);
+            if file.read_to_end(&mut buffer).is_err() {
+                return empty_response(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+
+            Response::builder()
+                .status(StatusCode::OK)
+                .header("Content-Type", "audio/webm")
+                .header("Accept-Ranges", "bytes")
+                .header("Content-Length", buffer.len().to_string())
+                .body(Cow::Owned(buffer))
+                .unwrap()
+        }
+    }
+}
+
+/// Parses a `Range: bytes=start-end` header value into an inclusive
+/// `(start, end)` byte range clamped to `total_len`. Handles the RFC 7233
+/// suffix form `bytes=-N` ("the last N bytes") as well as `start-` and
+/// `start-end`. Only a single range is supported; anything else (or an
+/// unsatisfiable range) is treated as "serve the whole file".
+fn parse_range(value: &str, total_len: u64) -> Option<(u64, u64)> {
+    let spec = value.strip_prefix("bytes=")?;
+
+    if let Some(suffix_len) = spec.strip_prefix('-') {
+        let suffix_len: u64 = suffix_len.parse().ok()?;
+        if suffix_len == 0 || total_len == 0 {
+            return None;
+        }
+        let start = total_len.saturating_sub(suffix_len);
+        return Some((start, total_len - 1));
+    }
+
+    let (start_str, end_str) = spec.split_once('-')?;
+    let start: u64 = start_str.parse().ok()?;
+    let end: u64 = if end_str.is_empty() {
+        total_len.saturating_sub(1)
+    } else {
+        end_str.parse().ok()?
+    };
+
+    if start > end || start >= total_len {
+        return None;
+    }
+
+    Some((start, end.min(total_len.saturating_sub(1))))
+}
+
+fn empty_response(status: StatusCode) -> Response<Cow<'static, [u8]>> {
+    Response::builder()
+        .status(status)
+        .body(Cow::Borrowed(&[] as &[u8]))
+        .unwrap()
+}